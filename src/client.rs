@@ -1,46 +1,125 @@
 //! Includes both `Client` and all of the RPC response types.
+//!
+//! `Client` speaks one-shot HTTP; [`ws::WsClient`] speaks persistent
+//! WebSocket. The `def_api_method!`-generated response types (`GetInfoResponse`,
+//! `GetBlockChainInfoResponse`, etc.) are shared by both, so callers can pick
+//! whichever transport suits them without touching the rest of their code.
 #[macro_use]
 mod defapi;
+mod ws;
 
 use crate::{ResponseResult, ZecAmount};
+use async_trait::async_trait;
 use reqwest;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::ops::RangeFrom;
+use std::path::{Path, PathBuf};
 
-/// A `Client` is used to make multiple requests to a specific zcashd RPC server. Requests are invoked by async methods that correspond to `zcashd` RPC API method names with request-specific parameters. Each such method has an associated response type.
-pub struct Client {
+pub use ws::WsClient;
+
+/// Sends a single already-framed JSON-RPC request body and returns the raw
+/// response body, without knowing anything about `RequestEnvelope`/
+/// `ResponseEnvelope` framing or the typed methods built on top of it.
+///
+/// `Client` sends through a boxed `Transport`, so tests can swap in a `MockTransport` that
+/// answers from prerecorded responses instead of talking to a live zcashd.
+#[async_trait]
+pub trait Transport {
+    /// Send `body` and return the raw (still-serialized) response body.
+    async fn send(&self, body: String) -> ResponseResult<String>;
+}
+
+/// The default `Transport`: one `reqwest` HTTP POST per request.
+struct ReqwestTransport {
     url: String,
     auth: String,
     reqcli: reqwest::Client,
+}
+
+#[async_trait]
+impl Transport for ReqwestTransport {
+    async fn send(&self, body: String) -> ResponseResult<String> {
+        let reqresp = self
+            .reqcli
+            .post(&self.url)
+            .header("Authorization", &self.auth)
+            .body(body)
+            .send()
+            .await?;
+        Ok(reqresp.text().await?)
+    }
+}
+
+/// A `Client` is used to make multiple requests to a specific zcashd RPC server. Requests are invoked by async methods that correspond to `zcashd` RPC API method names with request-specific parameters. Each such method has an associated response type.
+pub struct Client {
+    transport: Box<dyn Transport>,
     idit: RangeFrom<u64>,
 }
 
 impl Client {
     /// Construct a new `Client` with connection & authentication info.
     /// - `hostport` is a host/ip with an optional `:PORT` appended.
-    /// - `authcookie` is the contents of `~/.zcash/.cookie`.
+    /// - `authcookie` is an already base64-encoded `user:password` value, as
+    ///   sent in the `Authorization: Basic` header. To build a `Client` from
+    ///   zcashd's raw `.cookie` file instead, use `Client::from_cookie_file`.
     pub fn new(hostport: String, authcookie: String) -> Client {
-        Client {
+        Client::from_transport(ReqwestTransport {
             url: format!("http://{}/", hostport),
             auth: format!("Basic {}", authcookie),
             reqcli: reqwest::Client::new(),
+        })
+    }
+
+    /// Construct a `Client` backed by an arbitrary `Transport`, e.g. a
+    /// `MockTransport` in tests.
+    pub fn from_transport(transport: impl Transport + 'static) -> Client {
+        Client {
+            transport: Box::new(transport),
             idit: (0..),
         }
     }
 
-    /// Construct a `Client` using the values of the environment variables `"ZCASHRPC_HOST"` and `"ZCASHRPC_AUTH"` as the arguments to `Client::new`.
-    pub fn from_env() -> Result<Client, std::env::VarError> {
+    /// Construct a `Client` by reading zcashd's auth cookie file and base64-encoding
+    /// it into a `Basic` auth header, mirroring how zcashd's own RPC clients consume it.
+    /// - `hostport` is a host/ip with an optional `:PORT` appended.
+    /// - `path` is the location of the cookie file, whose contents are `__cookie__:<password>`.
+    pub fn from_cookie_file(hostport: String, path: impl AsRef<Path>) -> Result<Client, crate::Error> {
+        let cookie = std::fs::read_to_string(path)?;
+        let authcookie = base64::encode(cookie.trim());
+        Ok(Client::new(hostport, authcookie))
+    }
+
+    /// The default location zcashd writes its auth cookie to: `~/.zcash/.cookie`.
+    pub fn default_cookie_path() -> PathBuf {
+        dirs::home_dir()
+            .unwrap_or_default()
+            .join(".zcash")
+            .join(".cookie")
+    }
+
+    /// Construct a `Client` using the values of the environment variables `"ZCASHRPC_HOST"`
+    /// and `"ZCASHRPC_AUTH"` as the arguments to `Client::new`. If `ZCASHRPC_AUTH` is unset,
+    /// falls back to `Client::from_cookie_file`, reading from `"ZCASHRPC_COOKIE"` if set, or
+    /// `Client::default_cookie_path()` otherwise.
+    pub fn from_env() -> Result<Client, crate::Error> {
         use std::env::var;
 
         let host = var("ZCASHRPC_HOST")?;
-        let auth = var("ZCASHRPC_AUTH")?;
-        Ok(Client::new(host, auth))
+        match var("ZCASHRPC_AUTH") {
+            Ok(auth) => Ok(Client::new(host, auth)),
+            Err(_) => {
+                let cookie_path = var("ZCASHRPC_COOKIE")
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|_| Client::default_cookie_path());
+                Client::from_cookie_file(host, cookie_path)
+            }
+        }
     }
 
     async fn make_request<R>(
         &mut self,
-        method: &'static str,
+        method: &str,
         args: Vec<serde_json::Value>,
     ) -> ResponseResult<R>
     where
@@ -52,18 +131,105 @@ impl Client {
         };
 
         let id = self.idit.next().unwrap();
-        let reqresp = self
-            .reqcli
-            .post(&self.url)
-            .header("Authorization", &self.auth)
-            .body(&RequestEnvelope::wrap(id, method, args))
-            .send()
+        let text = self
+            .transport
+            .send(RequestEnvelope::wrap(id, method, args))
             .await?;
-        let text = reqresp.text().await?;
         let respenv: ResponseEnvelope = json::parse_value(json::parse_string(text)?)?;
         let resp = respenv.unwrap(id)?;
         Ok(resp)
     }
+
+    /// Invoke an arbitrary RPC method by name, for ad-hoc calls that don't
+    /// (yet) have a `def_api_method!`-generated typed method. Known method
+    /// names are dispatched to their typed method and re-encoded as `Value`
+    /// so the shape still reflects the modeled response; anything else is
+    /// passed straight through to zcashd and returned as raw `Value`.
+    pub async fn call(
+        &mut self,
+        method: &str,
+        args: Vec<serde_json::Value>,
+    ) -> ResponseResult<serde_json::Value> {
+        match method {
+            "getinfo" | "getblockchaininfo" if !args.is_empty() => {
+                Err(crate::Error::InvalidArguments(format!(
+                    "{} takes no arguments, got {}",
+                    method,
+                    args.len()
+                )))
+            }
+            "getinfo" => Ok(serde_json::to_value(self.getinfo().await?)?),
+            "getblockchaininfo" => Ok(serde_json::to_value(self.getblockchaininfo().await?)?),
+            _ => self.make_request(method, args).await,
+        }
+    }
+
+    /// Start accumulating calls to send together as a single JSON-RPC batch
+    /// request, cutting round-trips when many calls are needed (e.g.
+    /// fetching hundreds of blocks). See `BatchBuilder`.
+    pub fn batch(&mut self) -> BatchBuilder<'_> {
+        BatchBuilder {
+            client: self,
+            calls: Vec::new(),
+        }
+    }
+}
+
+/// Accumulates `(method, args)` calls to send as a single JSON-RPC batch
+/// request. Each call is assigned its own id from the owning `Client`'s
+/// `idit`, the same id-keyed dispatch the single-call path uses, so results
+/// come back matched to the call that produced them regardless of the order
+/// zcashd answers in. Construct via `Client::batch`.
+pub struct BatchBuilder<'a> {
+    client: &'a mut Client,
+    calls: Vec<(u64, String, Vec<serde_json::Value>)>,
+}
+
+impl<'a> BatchBuilder<'a> {
+    /// Queue a call to be sent when `send` is called.
+    pub fn push(&mut self, method: impl Into<String>, args: Vec<serde_json::Value>) -> &mut Self {
+        let id = self.client.idit.next().unwrap();
+        self.calls.push((id, method.into(), args));
+        self
+    }
+
+    /// Send all queued calls as a single JSON-RPC batch request, returning
+    /// each call's result in the order it was `push`ed.
+    pub async fn send(self) -> ResponseResult<Vec<ResponseResult<serde_json::Value>>> {
+        use crate::{
+            envelope::{RequestEnvelope, ResponseEnvelope},
+            json,
+        };
+
+        if self.calls.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let ids: Vec<u64> = self.calls.iter().map(|(id, _, _)| *id).collect();
+        let bodies: Vec<String> = self
+            .calls
+            .into_iter()
+            .map(|(id, method, args)| RequestEnvelope::wrap(id, &method, args))
+            .collect();
+
+        let text = self
+            .client
+            .transport
+            .send(format!("[{}]", bodies.join(",")))
+            .await?;
+        let respenvs: Vec<ResponseEnvelope> = json::parse_value(json::parse_string(text)?)?;
+
+        let mut by_id: std::collections::HashMap<u64, ResponseEnvelope> =
+            respenvs.into_iter().map(|env| (env.id(), env)).collect();
+
+        Ok(ids
+            .into_iter()
+            .map(|id| match by_id.remove(&id) {
+                Some(env) => env.unwrap(id),
+                None => Err(crate::Error::MissingBatchResponse(id)),
+            })
+            .collect())
+    }
 }
 
 def_api_method! {
@@ -147,3 +313,149 @@ pub struct Consensus {
     pub chaintip: String,
     pub nextblock: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// A `Transport` that answers every request from a table of prerecorded
+    /// JSON response bodies keyed by RPC method name, so the typed methods
+    /// above can be exercised without a live zcashd.
+    struct MockTransport {
+        responses: HashMap<&'static str, &'static str>,
+    }
+
+    #[async_trait]
+    impl Transport for MockTransport {
+        async fn send(&self, body: String) -> ResponseResult<String> {
+            let request: serde_json::Value = crate::json::parse_string(body)?;
+            let method = request["method"].as_str().unwrap_or_default();
+            let id = request["id"].clone();
+            let result = self
+                .responses
+                .get(method)
+                .unwrap_or_else(|| panic!("no mocked response for method {:?}", method));
+
+            Ok(format!(
+                r#"{{"id":{},"result":{},"error":null}}"#,
+                id, result
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn getblockchaininfo_decodes_value_pools_softforks_and_upgrades() {
+        let mut responses = HashMap::new();
+        responses.insert(
+            "getblockchaininfo",
+            r#"{
+                "chain": "main",
+                "blocks": 1,
+                "headers": 1,
+                "bestblockhash": "00000000000000000000000000000000000000000000000000000000000000",
+                "difficulty": 1.0,
+                "verificationprogress": 1.0,
+                "chainwork": "00",
+                "pruned": false,
+                "size_on_disk": 123,
+                "commitments": 1,
+                "valuePools": [
+                    {"id": "sprout", "monitored": true, "chainValue": 0.0, "chainValueZat": 0, "valueDelta": 0.0, "valueDeltaZat": 0}
+                ],
+                "softforks": [
+                    {"id": "bip34", "version": 2, "enforce": {"status": true, "found": 1, "required": 1, "window": 1000}, "reject": {"status": true, "found": 1, "required": 1, "window": 1000}}
+                ],
+                "upgrades": {
+                    "5ba81b19": {"name": "Overwinter", "activationheight": 1, "status": "active", "info": "..."}
+                },
+                "consensus": {"chaintip": "00", "nextblock": "00"},
+                "pruneheight": null,
+                "fullyNotified": true
+            }"#,
+        );
+        let mut client = Client::from_transport(MockTransport { responses });
+
+        let info = client.getblockchaininfo().await.unwrap();
+
+        assert_eq!(info.chain, "main");
+        assert_eq!(info.valuePools.len(), 1);
+        assert_eq!(info.valuePools[0].id, "sprout");
+        assert_eq!(info.softforks[0].enforce.required, 1);
+        assert_eq!(
+            info.upgrades.get("5ba81b19").unwrap().name,
+            "Overwinter"
+        );
+    }
+
+    /// A `Transport` for exercising `BatchBuilder`: parses the posted batch
+    /// body back into its individual requests and answers each one, by id,
+    /// from a table of prerecorded responses keyed by method name. The reply
+    /// lists the responses in reverse request order, so matching by position
+    /// instead of by id would fail. Methods listed in `drop` are omitted from
+    /// the reply entirely, to exercise the missing-response path.
+    struct BatchMockTransport {
+        responses: HashMap<&'static str, &'static str>,
+        drop: Vec<&'static str>,
+    }
+
+    #[async_trait]
+    impl Transport for BatchMockTransport {
+        async fn send(&self, body: String) -> ResponseResult<String> {
+            let requests: Vec<serde_json::Value> = crate::json::parse_string(body)?;
+            let mut results = Vec::new();
+            for request in requests {
+                let method = request["method"].as_str().unwrap_or_default();
+                if self.drop.contains(&method) {
+                    continue;
+                }
+                let id = request["id"].clone();
+                let result = self
+                    .responses
+                    .get(method)
+                    .unwrap_or_else(|| panic!("no mocked response for method {:?}", method));
+                results.push(format!(r#"{{"id":{},"result":{},"error":null}}"#, id, result));
+            }
+            results.reverse();
+            Ok(format!("[{}]", results.join(",")))
+        }
+    }
+
+    #[tokio::test]
+    async fn batch_builder_matches_results_by_id_and_preserves_push_order() {
+        let mut responses = HashMap::new();
+        responses.insert("getinfo", r#""first""#);
+        responses.insert("getblockchaininfo", r#""second""#);
+        let mut client = Client::from_transport(BatchMockTransport {
+            responses,
+            drop: Vec::new(),
+        });
+
+        let mut batch = client.batch();
+        batch.push("getinfo", vec![]);
+        batch.push("getblockchaininfo", vec![]);
+        let results = batch.send().await.unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap(), "first");
+        assert_eq!(results[1].as_ref().unwrap(), "second");
+    }
+
+    #[tokio::test]
+    async fn batch_builder_reports_a_missing_response_without_panicking() {
+        let mut client = Client::from_transport(BatchMockTransport {
+            responses: HashMap::new(),
+            drop: vec!["getinfo"],
+        });
+
+        let mut batch = client.batch();
+        batch.push("getinfo", vec![]);
+        let results = batch.send().await.unwrap();
+
+        assert_eq!(results.len(), 1);
+        match &results[0] {
+            Err(crate::Error::MissingBatchResponse(id)) => assert_eq!(*id, 0),
+            other => panic!("expected MissingBatchResponse, got {:?}", other),
+        }
+    }
+}
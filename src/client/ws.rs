@@ -0,0 +1,220 @@
+//! WebSocket transport for the zcashd JSON-RPC API.
+//!
+//! [`super::Client`] sends a separate HTTP POST for every request. `WsClient`
+//! instead keeps a single long-lived connection open: each outgoing call is
+//! framed as a text message carrying a `RequestEnvelope`, and incoming
+//! messages are demultiplexed back to the right caller by id. Any incoming
+//! message that doesn't match an outstanding call -- an unsolicited push
+//! notification such as a new-block event -- is handed to the
+//! `notifications` receiver returned alongside the `WsClient` by `connect`,
+//! which is what makes those events observable at all; a one-shot HTTP POST
+//! can never deliver them.
+//!
+//! The typed methods below (`getinfo`, `getblockchaininfo`, ...) return the
+//! exact same response structs as [`super::Client`]; only the plumbing that
+//! gets bytes to and from zcashd differs.
+
+use crate::{
+    envelope::{RequestEnvelope, ResponseEnvelope},
+    json, ResponseResult,
+};
+use futures_util::{Stream, SinkExt, StreamExt};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::ops::RangeFrom;
+use std::sync::Arc;
+use tokio::sync::{mpsc, oneshot, Mutex};
+use tokio_tungstenite::tungstenite::{http::Request, Message};
+
+type PendingMap = Arc<Mutex<HashMap<u64, oneshot::Sender<ResponseResult<ResponseEnvelope>>>>>;
+
+/// A `WsClient` is used to make multiple requests to a specific zcashd RPC
+/// server over a single persistent WebSocket connection. Responses (and
+/// unsolicited push notifications) may arrive in any order, so each request
+/// is tagged with an id from `idit` and handed to its awaiting caller through
+/// an id-keyed table of one-shot channels.
+pub struct WsClient {
+    outbound: mpsc::UnboundedSender<Message>,
+    pending: PendingMap,
+    idit: RangeFrom<u64>,
+}
+
+/// Reads `Message`s off `incoming` until it ends, routing each one to the
+/// `pending` call that's waiting on its id, or to `notify_tx` if nothing is
+/// (an unsolicited push notification, or a message with no id at all). When
+/// `incoming` ends, every call still left in `pending` is failed with
+/// `Error::ConnectionClosed` rather than left hanging forever.
+///
+/// Split out from `WsClient::connect` so the demultiplexing logic can be
+/// driven directly in tests with a fake message stream.
+async fn demux(
+    mut incoming: impl Stream<Item = Message> + Unpin,
+    pending: PendingMap,
+    notify_tx: mpsc::UnboundedSender<serde_json::Value>,
+) {
+    while let Some(msg) = incoming.next().await {
+        let text = match msg {
+            Message::Text(text) => text,
+            _ => continue,
+        };
+        let value: serde_json::Value = match json::parse_string(text) {
+            Ok(value) => value,
+            Err(_) => continue,
+        };
+
+        let id = value.get("id").and_then(serde_json::Value::as_u64);
+        let waiting = match id {
+            Some(id) => pending.lock().await.remove(&id),
+            None => None,
+        };
+
+        match waiting {
+            Some(waiting) => {
+                let _ = waiting.send(json::parse_value::<ResponseEnvelope>(value).map_err(Into::into));
+            }
+            // No call is waiting on this id (or it has none at all): it's an
+            // unsolicited push notification, not a response.
+            None => {
+                let _ = notify_tx.send(value);
+            }
+        }
+    }
+
+    // The connection is gone: nothing still in `pending` will ever get an
+    // answer, so fail them instead of leaving their `rx.await` parked forever.
+    for (_, waiting) in pending.lock().await.drain() {
+        let _ = waiting.send(Err(crate::Error::ConnectionClosed));
+    }
+}
+
+impl WsClient {
+    /// Open a `WsClient` connection with connection & authentication info.
+    /// - `hostport` is a host/ip with an optional `:PORT` appended.
+    /// - `authcookie` is an already base64-encoded `user:password` value, as
+    ///   sent in the `Authorization: Basic` header (see `Client::from_cookie_file`
+    ///   for building one from zcashd's raw `.cookie` file).
+    ///
+    /// Returns the `WsClient` alongside a receiver of every incoming message
+    /// that doesn't match an outstanding call -- i.e. unsolicited push
+    /// notifications such as new-block events. Drop the receiver if you
+    /// don't care about them; notifications are simply discarded once it's
+    /// gone.
+    pub async fn connect(
+        hostport: String,
+        authcookie: String,
+    ) -> ResponseResult<(WsClient, mpsc::UnboundedReceiver<serde_json::Value>)> {
+        let request = Request::builder()
+            .uri(format!("ws://{}/", hostport))
+            .header("Authorization", format!("Basic {}", authcookie))
+            .body(())?;
+
+        let (ws_stream, _) = tokio_tungstenite::connect_async(request).await?;
+        let (mut sink, mut stream) = ws_stream.split();
+        let (outbound, mut to_send) = mpsc::unbounded_channel::<Message>();
+        let pending: PendingMap = Arc::new(Mutex::new(HashMap::new()));
+        let (notify_tx, notify_rx) = mpsc::unbounded_channel::<serde_json::Value>();
+
+        tokio::spawn(async move {
+            while let Some(msg) = to_send.recv().await {
+                if sink.send(msg).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let incoming = Box::pin(stream.filter_map(|item| async move { item.ok() }));
+        tokio::spawn(demux(incoming, pending.clone(), notify_tx));
+
+        let client = WsClient {
+            outbound,
+            pending,
+            idit: (0..),
+        };
+        Ok((client, notify_rx))
+    }
+
+    async fn make_request<R>(
+        &mut self,
+        method: &str,
+        args: Vec<serde_json::Value>,
+    ) -> ResponseResult<R>
+    where
+        R: DeserializeOwned,
+    {
+        let id = self.idit.next().unwrap();
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().await.insert(id, tx);
+
+        self.outbound
+            .send(Message::Text(RequestEnvelope::wrap(id, method, args)))
+            .map_err(|_| crate::Error::ConnectionClosed)?;
+
+        let respenv = rx.await.map_err(|_| crate::Error::ConnectionClosed)??;
+        let resp = respenv.unwrap(id)?;
+        Ok(resp)
+    }
+
+    /// Fetch general information about the node and wallet state.
+    pub async fn getinfo(&mut self) -> ResponseResult<super::GetInfoResponse> {
+        self.make_request("getinfo", vec![]).await
+    }
+
+    /// Fetch information about the current state of the block chain.
+    pub async fn getblockchaininfo(&mut self) -> ResponseResult<super::GetBlockChainInfoResponse> {
+        self.make_request("getblockchaininfo", vec![]).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::stream;
+
+    fn envelope_text(id: u64) -> String {
+        format!(r#"{{"id":{},"result":"ok","error":null}}"#, id)
+    }
+
+    #[tokio::test]
+    async fn demux_routes_response_to_its_waiting_call() {
+        let pending: PendingMap = Arc::new(Mutex::new(HashMap::new()));
+        let (tx, rx) = oneshot::channel();
+        pending.lock().await.insert(7, tx);
+        let (notify_tx, _notify_rx) = mpsc::unbounded_channel();
+
+        let incoming = Box::pin(stream::iter(vec![Message::Text(envelope_text(7))]));
+        demux(incoming, pending, notify_tx).await;
+
+        let respenv = rx.await.unwrap().unwrap();
+        assert_eq!(respenv.id(), 7);
+    }
+
+    #[tokio::test]
+    async fn demux_forwards_unmatched_messages_as_notifications() {
+        let pending: PendingMap = Arc::new(Mutex::new(HashMap::new()));
+        let (notify_tx, mut notify_rx) = mpsc::unbounded_channel();
+
+        // id 9 has no waiting call, so it's a push notification, not a response.
+        let incoming = Box::pin(stream::iter(vec![Message::Text(envelope_text(9))]));
+        demux(incoming, pending, notify_tx).await;
+
+        let notification = notify_rx.try_recv().expect("expected a notification");
+        assert_eq!(notification["id"], 9);
+    }
+
+    #[tokio::test]
+    async fn demux_fails_outstanding_calls_when_the_stream_ends() {
+        let pending: PendingMap = Arc::new(Mutex::new(HashMap::new()));
+        let (tx, rx) = oneshot::channel();
+        pending.lock().await.insert(1, tx);
+        let (notify_tx, _notify_rx) = mpsc::unbounded_channel();
+
+        // The stream ends without ever producing a response for id 1.
+        let incoming = Box::pin(stream::iter(Vec::<Message>::new()));
+        demux(incoming, pending, notify_tx).await;
+
+        match rx.await.unwrap() {
+            Err(crate::Error::ConnectionClosed) => {}
+            other => panic!("expected ConnectionClosed, got {:?}", other),
+        }
+    }
+}
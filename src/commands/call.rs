@@ -0,0 +1,81 @@
+use crate::{application::app_config, config::ZcashRcliConfig, Client};
+use abscissa_core::{config, Command, FrameworkError, Options, Runnable};
+
+/// `call` subcommand
+///
+/// Invokes an arbitrary `zcashd` JSON-RPC method by name, e.g.:
+///
+/// ```text
+/// zcashrcli call getblockchaininfo
+/// zcashrcli call getblock "0000...0000" 2
+/// ```
+///
+/// Each argument after the method name is parsed as JSON where it's valid
+/// JSON (numbers, booleans, quoted strings, objects/arrays), and otherwise
+/// treated as a bare string, so `getblock abcd 2` and `getblock "abcd" 2`
+/// behave the same. Methods with a `def_api_method!`-generated response type
+/// are decoded into that struct; unmodeled methods fall back to raw JSON.
+#[derive(Command, Debug, Options)]
+pub struct CallCmd {
+    /// RPC method name, followed by its positional arguments.
+    #[options(free)]
+    args: Vec<String>,
+
+    /// `zcashd` host:port to connect to, overriding the config file.
+    #[options(long = "host")]
+    host: Option<String>,
+
+    /// Path to `zcashd`'s `.cookie` file, overriding the config file.
+    #[options(long = "cookie")]
+    cookie: Option<String>,
+}
+
+impl Runnable for CallCmd {
+    fn run(&self) {
+        let (method, args) = match self.args.split_first() {
+            Some((method, rest)) => (method.as_str(), rest),
+            None => {
+                eprintln!("Usage: zcashrcli call <method> [args...]");
+                return;
+            }
+        };
+        let args = args
+            .iter()
+            .map(|arg| serde_json::from_str(arg).unwrap_or_else(|_| arg.as_str().into()))
+            .collect();
+
+        let config = app_config();
+        let mut client = match Client::from_cookie_file(config.rpc.host.clone(), &config.rpc.cookie) {
+            Ok(client) => client,
+            Err(err) => {
+                eprintln!("error constructing client: {}", err);
+                return;
+            }
+        };
+
+        let runtime = tokio::runtime::Runtime::new().expect("failed to start tokio runtime");
+        match runtime.block_on(client.call(method, args)) {
+            Ok(response) => println!("{:#}", response),
+            Err(err) => eprintln!("error: {}", err),
+        }
+    }
+}
+
+impl config::Override<ZcashRcliConfig> for CallCmd {
+    // Process the given command line options, overriding settings from
+    // a configuration file using explicit flags taken from command-line
+    // arguments.
+    fn override_config(
+        &self,
+        mut config: ZcashRcliConfig,
+    ) -> Result<ZcashRcliConfig, FrameworkError> {
+        if let Some(host) = &self.host {
+            config.rpc.host = host.clone();
+        }
+        if let Some(cookie) = &self.cookie {
+            config.rpc.cookie = cookie.clone();
+        }
+
+        Ok(config)
+    }
+}
@@ -0,0 +1,45 @@
+//! `zcashrcli` subcommands
+
+mod call;
+
+use self::call::CallCmd;
+use crate::config::ZcashRcliConfig;
+use abscissa_core::{
+    config::Override, Command, Configurable, FrameworkError, Help, Options, Runnable,
+};
+use std::path::PathBuf;
+
+/// `zcashrcli` configuration filename
+pub const CONFIG_FILE: &str = "zcashrcli.toml";
+
+/// `zcashrcli` entry point: dispatches to the subcommand named on the command line.
+#[derive(Command, Debug, Options, Runnable)]
+pub enum EntryPoint {
+    /// Show help for a (sub)command.
+    #[options(help = "show help message")]
+    Help(Help<Self>),
+
+    /// Invoke an arbitrary `zcashd` RPC method by name, e.g.
+    /// `zcashrcli call getblockchaininfo`.
+    #[options(help = "invoke an RPC method by name")]
+    Call(CallCmd),
+}
+
+impl Configurable<ZcashRcliConfig> for EntryPoint {
+    fn config_path(&self) -> Option<PathBuf> {
+        let filename = PathBuf::from(CONFIG_FILE);
+
+        if filename.exists() {
+            Some(filename)
+        } else {
+            None
+        }
+    }
+
+    fn process_config(&self, config: ZcashRcliConfig) -> Result<ZcashRcliConfig, FrameworkError> {
+        match self {
+            EntryPoint::Call(cmd) => cmd.override_config(config),
+            _ => Ok(config),
+        }
+    }
+}
@@ -1,40 +0,0 @@
-use crate::{application::app_config, config::ZcashRcliConfig};
-use abscissa_core::{config, Command, FrameworkError, Options, Runnable};
-
-/// `start` subcommand
-///
-/// The `Options` proc macro generates an option parser based on the struct
-/// definition, and is defined in the `gumdrop` crate. See their documentation
-/// for a more comprehensive example:
-///
-/// <https://docs.rs/gumdrop/>
-#[derive(Command, Debug, Options)]
-pub struct StartCmd {
-    /// To whom are we saying hello?
-    #[options(free)]
-    recipient: Vec<String>,
-}
-
-impl Runnable for StartCmd {
-    /// Start the application.
-    fn run(&self) {
-        let config = app_config();
-        println!("Hello, {}!", &config.hello.recipient);
-    }
-}
-
-impl config::Override<ZcashRcliConfig> for StartCmd {
-    // Process the given command line options, overriding settings from
-    // a configuration file using explicit flags taken from command-line
-    // arguments.
-    fn override_config(
-        &self,
-        mut config: ZcashRcliConfig,
-    ) -> Result<ZcashRcliConfig, FrameworkError> {
-        if !self.recipient.is_empty() {
-            config.hello.recipient = self.recipient.join(" ");
-        }
-
-        Ok(config)
-    }
-}
@@ -0,0 +1,40 @@
+//! `zcashrcli` configuration.
+
+use crate::Client;
+use serde::{Deserialize, Serialize};
+
+/// `zcashrcli` configuration settings.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ZcashRcliConfig {
+    /// Connection settings for talking to a `zcashd` RPC server.
+    pub rpc: RpcConfig,
+}
+
+impl Default for ZcashRcliConfig {
+    fn default() -> Self {
+        Self {
+            rpc: RpcConfig::default(),
+        }
+    }
+}
+
+/// Connection settings for talking to a `zcashd` RPC server.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RpcConfig {
+    /// `zcashd` host:port to connect to.
+    pub host: String,
+
+    /// Path to `zcashd`'s `.cookie` auth file.
+    pub cookie: String,
+}
+
+impl Default for RpcConfig {
+    fn default() -> Self {
+        Self {
+            host: "127.0.0.1:8232".to_owned(),
+            cookie: Client::default_cookie_path().to_string_lossy().into_owned(),
+        }
+    }
+}
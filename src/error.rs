@@ -0,0 +1,44 @@
+//! The error type shared by every fallible operation in this crate.
+
+use thiserror::Error as ThisError;
+
+/// The error type returned by `Client`, `WsClient`, and their supporting types.
+#[derive(Debug, ThisError)]
+pub enum Error {
+    /// An HTTP request to zcashd failed.
+    #[error("HTTP request to zcashd failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    /// Establishing or using a WebSocket connection to zcashd failed.
+    #[error("WebSocket connection to zcashd failed: {0}")]
+    WebSocket(#[from] tokio_tungstenite::tungstenite::Error),
+
+    /// The WebSocket handshake request couldn't be built, e.g. an invalid host.
+    #[error("invalid WebSocket handshake request: {0}")]
+    Handshake(#[from] tokio_tungstenite::tungstenite::http::Error),
+
+    /// A `WsClient` call was still waiting on a response when the connection closed.
+    #[error("WebSocket connection to zcashd closed before a response arrived")]
+    ConnectionClosed,
+
+    /// zcashd's batched response omitted a result for one of the requests in the batch.
+    #[error("zcashd did not return a response for batched request id {0}")]
+    MissingBatchResponse(u64),
+
+    /// A JSON value couldn't be parsed, or didn't match the shape it was decoded into.
+    #[error("invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// Reading a file (e.g. zcashd's `.cookie` file) failed.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// A required environment variable was missing or not valid UTF-8.
+    #[error("environment variable error: {0}")]
+    Var(#[from] std::env::VarError),
+
+    /// `Client::call` was given arguments that don't match what the named
+    /// method expects.
+    #[error("{0}")]
+    InvalidArguments(String),
+}